@@ -0,0 +1,65 @@
+//! A higher-order component that bridges a [Store] for you, so a component's `Properties` can
+//! simply ask for the current state instead of hand-rolling a `Msg::State(Rc<Model>)` variant.
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::dispatch::{Dispatch, DispatchProps, DispatchPropsMut};
+use crate::store::Store;
+
+/// Properties that expose the current model of a [Store], set by [WithDispatch] whenever the
+/// store changes.
+pub trait StateView<S: Store> {
+    fn state(&self) -> Rc<S::Model>;
+    fn set_state(&mut self, state: Rc<S::Model>);
+}
+
+/// Wraps a component `C`, bridging `C::Properties`'s [Store] for it and keeping the `dispatch`
+/// and `state` fields of `C::Properties` in sync as the store changes.
+pub struct WithDispatch<C>
+where
+    C: Component,
+    C::Properties: DispatchPropsMut + StateView<<C::Properties as DispatchProps>::Store>,
+{
+    props: C::Properties,
+    dispatch: Dispatch<<C::Properties as DispatchProps>::Store>,
+}
+
+pub enum Msg<S: Store> {
+    State(Rc<S::Model>),
+}
+
+impl<C> Component for WithDispatch<C>
+where
+    C: Component,
+    C::Properties: DispatchPropsMut + StateView<<C::Properties as DispatchProps>::Store>,
+{
+    type Message = Msg<<C::Properties as DispatchProps>::Store>;
+    type Properties = C::Properties;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(Msg::State));
+        let mut props = ctx.props().clone();
+        *props.dispatch_mut() = dispatch.clone();
+
+        Self { props, dispatch }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let Msg::State(state) = msg;
+        self.props.set_state(state);
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        let mut props = ctx.props().clone();
+        *props.dispatch_mut() = self.dispatch.clone();
+        props.set_state(self.props.state());
+        self.props = props;
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! { <C ..self.props.clone() /> }
+    }
+}
@@ -0,0 +1,226 @@
+//! Handle for dispatching against, and receiving updates from, a [Store].
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use yew::agent::{Bridge, Bridged};
+use yew::Callback;
+
+use crate::service::{Selector, ServiceInput, ServiceOutput, StoreService, DEFAULT_SCOPE};
+use crate::store::Store;
+
+/// A handle to a [Store], used to read and mutate its shared state.
+///
+/// Cloning a `Dispatch` is cheap and shares the same underlying bridge, so it's fine to stash a
+/// clone wherever it's needed (a callback, a child component's props, etc).
+pub struct Dispatch<S: Store> {
+    bridge: Rc<RefCell<Box<dyn Bridge<StoreService<S>>>>>,
+    key: Rc<str>,
+    callbacks: Rc<RefCell<HashMap<Rc<str>, Callback<()>>>>,
+}
+
+impl<S: Store> Clone for Dispatch<S> {
+    fn clone(&self) -> Self {
+        Self {
+            bridge: Rc::clone(&self.bridge),
+            key: Rc::clone(&self.key),
+            callbacks: Rc::clone(&self.callbacks),
+        }
+    }
+}
+
+impl<S: Store> Dispatch<S> {
+    /// Bridge to the store, receiving its current state immediately and again every time it
+    /// changes.
+    pub fn bridge_state(callback: Callback<Rc<S::Model>>) -> Self {
+        Self::scoped(DEFAULT_SCOPE).bridge_state(callback)
+    }
+
+    /// Bridge to the store like [Dispatch::bridge_state], but only invoke `callback` when the
+    /// value `selector` computes from the model actually changes, rather than on every reduce.
+    ///
+    /// Useful for subscribing to one slice of a large shared model without re-rendering on
+    /// changes to unrelated parts of it.
+    pub fn bridge_selector<T>(
+        selector: impl Fn(&S::Model) -> T + 'static,
+        callback: Callback<T>,
+    ) -> Self
+    where
+        T: PartialEq + Clone + 'static,
+    {
+        Self::scoped(DEFAULT_SCOPE).bridge_selector(selector, callback)
+    }
+
+    /// Address a keyed instance of this store, so it can be bridged independently of the
+    /// default, unscoped instance.
+    ///
+    /// Each distinct key owns its own model and subscriber set -- `Dispatch::<BasicStore<State>>::scoped("sidebar")`
+    /// and `Dispatch::<BasicStore<State>>::scoped("main")` never see each other's state, letting
+    /// you reuse one `Store` type for repeated widgets (tabs, list rows, ...) that each need
+    /// their own private shared state.
+    ///
+    /// Unlike the default, unscoped instance (which lives for the app's entire lifetime), a
+    /// keyed instance's model is dropped once its last bridge disconnects -- so unmounting the
+    /// last widget using a given key frees that key's state rather than leaking it forever.
+    pub fn scoped(key: impl Into<Rc<str>>) -> Scope<S> {
+        Scope {
+            key: key.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Mutate the store's model in place.
+    ///
+    /// The change isn't applied synchronously -- it's sent to the store's service, which will
+    /// notify this (and every other) bridge on the same scope once it's done. A bridge made with
+    /// [Dispatch::bridge_selector] is only notified if its selected value changed.
+    pub fn reduce(&self, f: impl FnOnce(&mut S::Model) + 'static) {
+        self.bridge.borrow_mut().send(ServiceInput::Apply(
+            Rc::clone(&self.key),
+            Box::new(move |model| {
+                let mut model = (*model).clone();
+                f(&mut model);
+                Rc::new(model)
+            }),
+        ));
+    }
+
+    /// Create a [Callback] that calls [Dispatch::reduce] with `f` when emitted.
+    ///
+    /// Allocates a new `Callback` every time it's called -- fine for a callback built once (in a
+    /// struct component's `create`, say), but creating one fresh in `view` on every render
+    /// produces a new `Callback` identity each time, which defeats `PartialEq`-based diffing in
+    /// any memoized child it's passed to. Use [Dispatch::reduce_callback_with_id] there instead.
+    pub fn reduce_callback(&self, f: impl Fn(&mut S::Model) + 'static) -> Callback<()> {
+        let dispatch = self.clone();
+        let f = Rc::new(f);
+        Callback::from(move |_| {
+            let f = Rc::clone(&f);
+            dispatch.reduce(move |model| f(model));
+        })
+    }
+
+    /// Like [Dispatch::reduce_callback], but cache the resulting `Callback` under `id`: calling
+    /// this again with the same id returns a clone of the cached `Callback` instead of
+    /// allocating a new one.
+    ///
+    /// This keeps the `Callback`'s identity stable across renders, so a memoized child component
+    /// that receives it as a prop can skip re-rendering when only unrelated parent state changed.
+    ///
+    /// **`id` must map 1:1 to a closure whose captures never change.** On a cache hit, `f` is
+    /// discarded in favor of the cached `Callback` -- if you reuse an `id` across renders for a
+    /// closure that captures different state each time (e.g. a per-row callback capturing that
+    /// row's id, where the set of rows itself can change), every call after the first will keep
+    /// invoking the *original* capture, silently. Derive `id` from whatever `f` captures (e.g.
+    /// the row's own id) so a genuinely different closure always gets a different cache entry.
+    pub fn reduce_callback_with_id(
+        &self,
+        id: impl Into<Rc<str>>,
+        f: impl Fn(&mut S::Model) + 'static,
+    ) -> Callback<()> {
+        let id = id.into();
+
+        if let Some(callback) = self.callbacks.borrow().get(&id) {
+            return callback.clone();
+        }
+
+        let callback = self.reduce_callback(f);
+        self.callbacks.borrow_mut().insert(id, callback.clone());
+        callback
+    }
+}
+
+/// A specific, keyed instance of a [Store], returned by [Dispatch::scoped].
+pub struct Scope<S: Store> {
+    key: Rc<str>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: Store> Scope<S> {
+    /// Bridge to this scope's instance, the same as [Dispatch::bridge_state] does for the
+    /// default one.
+    pub fn bridge_state(&self, callback: Callback<Rc<S::Model>>) -> Dispatch<S> {
+        let bridge = StoreService::bridge(Callback::from(move |output: ServiceOutput<S>| {
+            if let ServiceOutput::Model(model) = output {
+                callback.emit(model);
+            }
+        }));
+
+        let dispatch = Dispatch {
+            bridge: Rc::new(RefCell::new(bridge)),
+            key: Rc::clone(&self.key),
+            callbacks: Default::default(),
+        };
+
+        dispatch
+            .bridge
+            .borrow_mut()
+            .send(ServiceInput::Connect(Rc::clone(&self.key)));
+
+        dispatch
+    }
+
+    /// Bridge to this scope's instance, the same as [Dispatch::bridge_selector] does for the
+    /// default one.
+    pub fn bridge_selector<T>(
+        &self,
+        selector: impl Fn(&S::Model) -> T + 'static,
+        callback: Callback<T>,
+    ) -> Dispatch<S>
+    where
+        T: PartialEq + Clone + 'static,
+    {
+        let select: Rc<dyn Fn(&S::Model) -> T> = Rc::new(selector);
+        let initial_select = Rc::clone(&select);
+
+        let bridge = StoreService::bridge(Callback::from(move |output: ServiceOutput<S>| {
+            match output {
+                // The first message every bridge receives is the full model on connect; use it
+                // to compute (and emit) the selector's initial value.
+                ServiceOutput::Model(model) => callback.emit(initial_select(&model)),
+                ServiceOutput::Selected(value) => {
+                    if let Ok(value) = value.downcast::<T>() {
+                        callback.emit(*value);
+                    }
+                }
+            }
+        }));
+
+        let dispatch = Dispatch {
+            bridge: Rc::new(RefCell::new(bridge)),
+            key: Rc::clone(&self.key),
+            callbacks: Default::default(),
+        };
+
+        {
+            let mut bridge = dispatch.bridge.borrow_mut();
+            bridge.send(ServiceInput::Connect(Rc::clone(&self.key)));
+            bridge.send(ServiceInput::Subscribe(
+                Rc::clone(&self.key),
+                Box::new(Selector::new(select)),
+            ));
+        }
+
+        dispatch
+    }
+}
+
+/// Implemented by anything that owns a [Dispatch], so helpers can be generic over "has a
+/// dispatch" rather than demanding a concrete field.
+pub trait Dispatcher<S: Store> {
+    fn dispatch(&self) -> &Dispatch<S>;
+}
+
+/// Properties that expose a [Dispatch], typically implemented by a component's `Properties`.
+pub trait DispatchProps: Clone + PartialEq {
+    type Store: Store;
+
+    fn dispatch(&self) -> &Dispatch<Self::Store>;
+}
+
+/// [DispatchProps] that can also be mutated in place, for components that hold their own
+/// `Dispatch` rather than receiving one as a prop.
+pub trait DispatchPropsMut: DispatchProps {
+    fn dispatch_mut(&mut self) -> &mut Dispatch<Self::Store>;
+}
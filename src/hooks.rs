@@ -0,0 +1,62 @@
+//! Hooks for using a [Store] from a function component.
+use std::rc::Rc;
+
+use yew::functional::{use_ref, use_state};
+use yew::Callback;
+
+use crate::dispatch::Dispatch;
+use crate::store::Store;
+
+/// Subscribe the calling function component to `S`, re-rendering it whenever the store's model
+/// changes.
+///
+/// This is the function-component equivalent of bridging a store with
+/// [Dispatch::bridge_state](crate::dispatch::Dispatch::bridge_state) in a struct component's
+/// `create`, without needing a `Msg::State(Rc<Model>)` variant to receive it:
+///
+/// ```ignore
+/// #[function_component(Counter)]
+/// fn counter() -> Html {
+///     let (state, dispatch) = use_store::<BasicStore<State>>();
+///     let incr = dispatch.reduce_callback(|s| s.count += 1);
+///
+///     html! { <button onclick={incr}>{ state.count }</button> }
+/// }
+/// ```
+///
+/// The [Dispatch] is created once, on the component's first render, and kept alive for as long
+/// as the component is mounted. It's dropped along with the rest of the hook's persistent state
+/// when the component unmounts, which disconnects its bridge and removes it from the store's
+/// subscriber list -- there's nothing to tear down by hand.
+pub fn use_store<S: Store>() -> (Rc<S::Model>, Dispatch<S>) {
+    let model = use_state(Rc::<S::Model>::default);
+
+    let dispatch = use_ref({
+        let model = model.clone();
+        move || Dispatch::<S>::bridge_state(Callback::from(move |new_model| model.set(new_model)))
+    });
+
+    ((*model).clone(), (*dispatch).clone())
+}
+
+/// Like [use_store], but only re-renders the calling component when the value `selector`
+/// computes from the model changes, rather than on every change to the store.
+///
+/// `selector` is only read on the component's first render -- pass a closure that doesn't
+/// capture component-local state that can change across renders.
+pub fn use_selector<S, T>(selector: impl Fn(&S::Model) -> T + 'static) -> (T, Dispatch<S>)
+where
+    S: Store,
+    T: PartialEq + Clone + Default + 'static,
+{
+    let selected = use_state(T::default);
+
+    let dispatch = use_ref({
+        let selected = selected.clone();
+        move || {
+            Dispatch::<S>::bridge_selector(selector, Callback::from(move |value| selected.set(value)))
+        }
+    });
+
+    ((*selected).clone(), (*dispatch).clone())
+}
@@ -85,6 +85,8 @@
 
 pub mod component;
 pub mod dispatch;
+pub mod hooks;
+pub mod middleware;
 mod service;
 pub mod store;
 
@@ -96,6 +98,8 @@ pub mod prelude {
 
     pub use crate::component::{StateView, WithDispatch};
     pub use crate::dispatch::{Dispatch, DispatchProps, DispatchPropsMut, Dispatcher};
+    pub use crate::hooks::{use_selector, use_store};
+    pub use crate::middleware::{Logger, Middleware, Reduction, Thunk};
     pub use crate::store::{
         basic::BasicStore,
         persistent::{Persistent, PersistentStore},
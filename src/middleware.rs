@@ -0,0 +1,97 @@
+//! Hooks for observing (and reacting to) every change to a [Store]'s model, Redux-style.
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::store::{Store, StoreLink};
+
+/// A reduction a [Thunk] dispatches once its effect resolves.
+pub type Reduction<S> = Box<dyn FnOnce(&mut <S as Store>::Model)>;
+
+/// Observes every reduction a store's model goes through: called with the model just before
+/// (`prev`) and just after (`next`) the change, after it's been applied but before subscribers
+/// are notified of it.
+///
+/// Register a chain for a store by overriding [Store::middleware].
+pub trait Middleware<S: Store> {
+    /// `link` can be used to schedule a follow-up reduce, the same way a [Store] itself can with
+    /// [StoreLink::reduce] -- useful for async effects.
+    fn on_reduce(&self, prev: &Rc<S::Model>, next: &Rc<S::Model>, link: &StoreLink<S>);
+}
+
+/// Logs every `prev`/`next` diff to the browser console.
+pub struct Logger {
+    name: &'static str,
+}
+
+impl Logger {
+    /// `name` is included in every logged line, to tell stores apart when more than one uses
+    /// this middleware.
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl<S: Store> Middleware<S> for Logger
+where
+    S::Model: std::fmt::Debug,
+{
+    fn on_reduce(&self, prev: &Rc<S::Model>, next: &Rc<S::Model>, _link: &StoreLink<S>) {
+        web_sys::console::log_1(&format!("[{}] {:#?} -> {:#?}", self.name, prev, next).into());
+    }
+}
+
+/// Runs an async effect on every reduce, dispatching a follow-up reduce if and when it resolves
+/// to one.
+///
+/// `effect` is called on *every* reduce (including the one a [Thunk]-dispatched follow-up reduce
+/// itself causes), so it must decide for itself whether there's anything to do -- e.g. by
+/// diffing `prev`/`next` -- and return `None` rather than unconditionally producing a
+/// [Reduction]. An effect that always returns `Some` regardless of what changed will re-arm
+/// itself forever, since its own follow-up reduce re-triggers this same middleware.
+///
+/// ```ignore
+/// impl Store for MyStore {
+///     fn middleware() -> Vec<Box<dyn Middleware<Self>>> {
+///         vec![Box::new(Thunk::new(|prev: &Rc<MyModel>, next: &Rc<MyModel>| {
+///             // Only fetch when the selection actually changed, or this would loop forever.
+///             let id = (prev.selected_id != next.selected_id).then(|| next.selected_id);
+///             async move {
+///                 let id = id?;
+///                 let data = fetch_data(id).await;
+///                 Some(Box::new(move |model: &mut MyModel| model.data = Some(data)) as Reduction<Self>)
+///             }
+///         }))]
+///     }
+/// }
+/// ```
+pub struct Thunk<S: Store> {
+    #[allow(clippy::type_complexity)]
+    effect: Box<
+        dyn Fn(&Rc<S::Model>, &Rc<S::Model>) -> Pin<Box<dyn Future<Output = Option<Reduction<S>>>>>,
+    >,
+}
+
+impl<S: Store> Thunk<S> {
+    pub fn new<Fut>(effect: impl Fn(&Rc<S::Model>, &Rc<S::Model>) -> Fut + 'static) -> Self
+    where
+        Fut: Future<Output = Option<Reduction<S>>> + 'static,
+    {
+        Self {
+            effect: Box::new(move |prev, next| Box::pin(effect(prev, next))),
+        }
+    }
+}
+
+impl<S: Store> Middleware<S> for Thunk<S> {
+    fn on_reduce(&self, prev: &Rc<S::Model>, next: &Rc<S::Model>, link: &StoreLink<S>) {
+        let effect = (self.effect)(prev, next);
+        let link = link.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(reduction) = effect.await {
+                link.reduce(move |model| reduction(model));
+            }
+        });
+    }
+}
@@ -0,0 +1,200 @@
+//! The internal agent that keeps one instance of each [Store] per scope key alive, and fans each
+//! scope's model out to every [Dispatch](crate::dispatch::Dispatch) bridged to it.
+//!
+//! The default (unscoped) scope lives for the app's entire lifetime, same as a single
+//! global-per-type store always has. A keyed scope (see [Dispatch::scoped](crate::dispatch::Dispatch::scoped))
+//! is only kept around for as long as something is bridged to it, and is dropped -- along with
+//! its model -- once its last subscriber disconnects.
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use yew::agent::{Agent, AgentLink, Context, HandlerId};
+
+use crate::middleware::Middleware;
+use crate::store::{Store, StoreLink};
+
+pub(crate) type Reducer<S> = Box<dyn FnOnce(Rc<<S as Store>::Model>) -> Rc<<S as Store>::Model>>;
+
+/// The scope key every unscoped [Dispatch](crate::dispatch::Dispatch) uses.
+pub(crate) const DEFAULT_SCOPE: &str = "";
+
+/// A subscriber's view into the model, narrowed down by a selector function, so it can be
+/// notified only when the slice it actually cares about changes.
+///
+/// Type-erased so [StoreService] can hold selectors of unrelated output types in the same
+/// subscriber map; `T` is recovered by the subscriber's own bridge, which is the only place that
+/// still knows it statically.
+pub(crate) trait AnySelector<S: Store> {
+    /// Recompute the selected value against `model`, returning it only if it differs from the
+    /// last value this selector emitted (or hasn't emitted one yet).
+    fn notify(&mut self, model: &Rc<S::Model>) -> Option<Box<dyn Any>>;
+}
+
+pub(crate) struct Selector<S: Store, T> {
+    select: Rc<dyn Fn(&S::Model) -> T>,
+    last: Option<T>,
+}
+
+impl<S: Store, T> Selector<S, T> {
+    pub(crate) fn new(select: Rc<dyn Fn(&S::Model) -> T>) -> Self {
+        Self { select, last: None }
+    }
+}
+
+impl<S: Store, T: PartialEq + Clone + 'static> AnySelector<S> for Selector<S, T> {
+    fn notify(&mut self, model: &Rc<S::Model>) -> Option<Box<dyn Any>> {
+        let next = (self.select)(model);
+        let changed = self.last.as_ref().map_or(true, |last| *last != next);
+        self.last = Some(next.clone());
+
+        if changed {
+            Some(Box::new(next))
+        } else {
+            None
+        }
+    }
+}
+
+/// A connected bridge, either plain (wants every model) or narrowed to a selector.
+enum Subscriber<S: Store> {
+    Model,
+    Selector(Box<dyn AnySelector<S>>),
+}
+
+/// One scope's independent store instance, its middleware chain, and its own subscriber set.
+struct Scope<S: Store> {
+    store: S,
+    middleware: Vec<Box<dyn Middleware<S>>>,
+    subscribers: HashMap<HandlerId, Subscriber<S>>,
+}
+
+impl<S: Store> Scope<S> {
+    fn new(link: &AgentLink<StoreService<S>>, key: &Rc<str>) -> Self {
+        Self {
+            store: S::new(StoreLink::new(link.clone(), Rc::clone(key))),
+            middleware: S::middleware(),
+            subscribers: Default::default(),
+        }
+    }
+}
+
+pub(crate) enum ServiceInput<S: Store> {
+    /// Connect a subscriber to a scope, creating it if it doesn't exist yet, and send back its
+    /// current model.
+    Connect(Rc<str>),
+    /// Apply a reduction to a scope's model.
+    Apply(Rc<str>, Reducer<S>),
+    /// Narrow an already-connected subscriber down to a selector.
+    Subscribe(Rc<str>, Box<dyn AnySelector<S>>),
+}
+
+pub(crate) enum ServiceOutput<S: Store> {
+    /// A scope's current model.
+    Model(Rc<S::Model>),
+    /// A selector's newly-changed value, for the subscriber that registered it.
+    Selected(Box<dyn Any>),
+}
+
+pub(crate) struct StoreService<S: Store> {
+    link: AgentLink<Self>,
+    scopes: HashMap<Rc<str>, Scope<S>>,
+    /// Which scope each connected subscriber belongs to, so `disconnected` can find it.
+    subscriber_scopes: HashMap<HandlerId, Rc<str>>,
+}
+
+impl<S: Store> StoreService<S> {
+    fn scope(&mut self, key: &Rc<str>) -> &mut Scope<S> {
+        if !self.scopes.contains_key(key) {
+            let scope = Scope::new(&self.link, key);
+            self.scopes.insert(Rc::clone(key), scope);
+        }
+        self.scopes.get_mut(key).unwrap()
+    }
+}
+
+impl<S: Store> Agent for StoreService<S> {
+    type Reach = Context<Self>;
+    type Message = ();
+    type Input = ServiceInput<S>;
+    type Output = ServiceOutput<S>;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            scopes: Default::default(),
+            subscriber_scopes: Default::default(),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {}
+
+    fn connected(&mut self, _id: HandlerId) {
+        // We don't yet know which scope this subscriber belongs to -- every bridge sends an
+        // explicit `Connect(key)` right after connecting, which is handled below.
+    }
+
+    fn handle_input(&mut self, msg: Self::Input, id: HandlerId) {
+        match msg {
+            ServiceInput::Connect(key) => {
+                let model = self.scope(&key).store.model();
+                self.scope(&key).subscribers.insert(id, Subscriber::Model);
+                self.subscriber_scopes.insert(id, key);
+                self.link.respond(id, ServiceOutput::Model(model));
+            }
+            ServiceInput::Apply(key, reduce) => {
+                // Clone the link up front so responding inside the loop below doesn't need to
+                // borrow `self` again while `scope` (borrowed from `self`) is still live.
+                let link = self.link.clone();
+                let store_link = StoreLink::new(link.clone(), Rc::clone(&key));
+
+                let scope = self.scope(&key);
+                let prev = scope.store.model();
+                let model = reduce(Rc::clone(&prev));
+                scope.store.set_model(Rc::clone(&model));
+
+                for middleware in scope.middleware.iter() {
+                    middleware.on_reduce(&prev, &model, &store_link);
+                }
+
+                for (id, subscriber) in scope.subscribers.iter_mut() {
+                    match subscriber {
+                        Subscriber::Model => {
+                            link.respond(*id, ServiceOutput::Model(Rc::clone(&model)))
+                        }
+                        Subscriber::Selector(selector) => {
+                            if let Some(value) = selector.notify(&model) {
+                                link.respond(*id, ServiceOutput::Selected(value));
+                            }
+                        }
+                    }
+                }
+            }
+            ServiceInput::Subscribe(key, mut selector) => {
+                // Seed the selector's cache against the current model without notifying --
+                // the subscriber already received the current value via `Connect`.
+                let scope = self.scope(&key);
+                selector.notify(&scope.store.model());
+                scope.subscribers.insert(id, Subscriber::Selector(selector));
+                self.subscriber_scopes.insert(id, key);
+            }
+        }
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        if let Some(key) = self.subscriber_scopes.remove(&id) {
+            if let Some(scope) = self.scopes.get_mut(&key) {
+                scope.subscribers.remove(&id);
+
+                // The default scope is kept for the app's entire lifetime, same as a single
+                // global-per-type store always has been -- but a keyed scope exists only for as
+                // long as something is actually bridged to it, so e.g. a list of rows that
+                // mount/unmount distinct keys doesn't leak a dead store per key that ever
+                // existed.
+                if scope.subscribers.is_empty() && &*key != DEFAULT_SCOPE {
+                    self.scopes.remove(&key);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! The simplest possible [Store]: the model *is* the state, with no extra behavior.
+use std::rc::Rc;
+
+use super::{Store, StoreLink};
+
+/// A store that holds a model directly, with no additional reducer or persistence logic.
+///
+/// This is what the example in the crate root uses: `Dispatch<BasicStore<State>>` lets any
+/// number of components share a `State` with no boilerplate beyond defining `State` itself.
+pub struct BasicStore<T> {
+    model: Rc<T>,
+}
+
+impl<T: Clone + Default + 'static> Store for BasicStore<T> {
+    type Model = T;
+
+    fn new(_link: StoreLink<Self>) -> Self {
+        Self {
+            model: Default::default(),
+        }
+    }
+
+    fn model(&self) -> Rc<T> {
+        Rc::clone(&self.model)
+    }
+
+    fn set_model(&mut self, model: Rc<T>) {
+        self.model = model;
+    }
+}
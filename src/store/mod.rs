@@ -0,0 +1,93 @@
+//! Types for defining a shared state store.
+use std::rc::Rc;
+
+pub mod basic;
+pub mod persistent;
+pub mod reducer;
+
+use yew::agent::AgentLink;
+
+use crate::middleware::Middleware;
+use crate::service::{ServiceInput, StoreService};
+
+/// A value that can report whether it meaningfully differs from another value of the same type.
+///
+/// This is a small replacement for the `neq_assign` pattern older Yew apps relied on: instead of
+/// comparing `Rc`s by pointer, compare the state they point to.
+pub trait Changed {
+    fn changed(&self, other: &Self) -> bool;
+}
+
+impl<T: PartialEq> Changed for T {
+    fn changed(&self, other: &Self) -> bool {
+        self != other
+    }
+}
+
+/// Handle given to a [Store] when it is created, letting it talk back to its own
+/// [StoreService](crate::service::StoreService) (for example to schedule a reduction from
+/// outside of `reduce` itself).
+pub struct StoreLink<S: Store> {
+    link: AgentLink<StoreService<S>>,
+    key: Rc<str>,
+}
+
+impl<S: Store> Clone for StoreLink<S> {
+    fn clone(&self) -> Self {
+        Self {
+            link: self.link.clone(),
+            key: Rc::clone(&self.key),
+        }
+    }
+}
+
+impl<S: Store> StoreLink<S> {
+    pub(crate) fn new(link: AgentLink<StoreService<S>>, key: Rc<str>) -> Self {
+        Self { link, key }
+    }
+
+    /// The scope key this store instance was created for (see [Dispatch::scoped](crate::dispatch::Dispatch::scoped)).
+    /// Unscoped stores get the empty string.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Schedule a reduction against this store's model, the same way
+    /// [Dispatch::reduce](crate::dispatch::Dispatch::reduce) does.
+    pub fn reduce(&self, f: impl FnOnce(&mut S::Model) + 'static) {
+        self.link.send_input(ServiceInput::Apply(
+            Rc::clone(&self.key),
+            Box::new(move |model| {
+                let mut model = (*model).clone();
+                f(&mut model);
+                Rc::new(model)
+            }),
+        ));
+    }
+}
+
+/// A shared state container.
+///
+/// One instance of each `Store` type is kept per scope key (see
+/// [StoreService](crate::service::StoreService)); every [Dispatch](crate::dispatch::Dispatch)
+/// bridged to the same scope reads and writes the same [Store::Model]. The default instance is
+/// kept for the lifetime of the app; a keyed one is dropped once nothing is bridged to it.
+pub trait Store: Sized + 'static {
+    /// The state this store manages.
+    type Model: Clone + Default + 'static;
+
+    /// Create a new instance of this store.
+    fn new(link: StoreLink<Self>) -> Self;
+
+    /// The current state.
+    fn model(&self) -> Rc<Self::Model>;
+
+    /// Replace the current state.
+    fn set_model(&mut self, model: Rc<Self::Model>);
+
+    /// The [Middleware] chain to run, in order, after every reduce and before subscribers are
+    /// notified of it. Empty by default.
+    fn middleware() -> Vec<Box<dyn Middleware<Self>>> {
+        Vec::new()
+    }
+}
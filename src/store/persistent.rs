@@ -0,0 +1,141 @@
+//! A [Store] that keeps its model mirrored in browser storage, so it survives a reload.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::StorageEvent;
+use yew_services::storage::{Area, StorageService};
+
+use super::{Store, StoreLink};
+
+/// A model that can be persisted to [StorageService].
+pub trait Persistent: Serialize + DeserializeOwned + Clone + Default + 'static {
+    /// Which storage area to persist to. Defaults to [Area::Local].
+    fn area() -> Area {
+        Area::Local
+    }
+
+    /// Opt in to cross-tab sync: when another tab changes this store's persisted value, pick
+    /// up the change here too instead of waiting for a reload. Defaults to `false`.
+    fn sync_tabs() -> bool {
+        false
+    }
+}
+
+/// A store whose model is loaded from storage on creation, and written back on every change.
+///
+/// Each distinct [scope](crate::dispatch::Dispatch::scoped) gets its own storage slot, so keyed
+/// instances of the same `T` don't clobber each other's persisted state. If [Persistent::sync_tabs]
+/// returns `true`, changes made to this slot from another browser tab are picked up live, via the
+/// `window`'s `storage` event, and fanned out to this store's subscribers exactly as a local
+/// [Dispatch::reduce](crate::dispatch::Dispatch::reduce) would be.
+pub struct PersistentStore<T> {
+    model: Rc<T>,
+    storage: StorageService,
+    storage_key: String,
+    /// The last value we know to be in storage, whether we wrote it ourselves or picked it up
+    /// from another tab. Lets the `storage` listener ignore events that just echo a change this
+    /// store already knows about.
+    last_serialized: Rc<RefCell<String>>,
+    /// Kept alive for as long as the store is; dropping it removes the listener.
+    _listener: Option<Closure<dyn FnMut(StorageEvent)>>,
+}
+
+impl<T: Persistent> Store for PersistentStore<T> {
+    type Model = T;
+
+    fn new(link: StoreLink<Self>) -> Self {
+        let storage_key = format!("{}::{}", std::any::type_name::<T>(), link.key());
+        let storage =
+            StorageService::new(T::area()).expect("storage service backend is unavailable");
+
+        let model: T = storage
+            .restore(&storage_key)
+            .ok()
+            .and_then(|stored| serde_json::from_str(&stored).ok())
+            .unwrap_or_default();
+
+        let last_serialized = Rc::new(RefCell::new(
+            serde_json::to_string(&model).unwrap_or_default(),
+        ));
+
+        let listener = if T::sync_tabs() {
+            Some(Self::listen(storage_key.clone(), Rc::clone(&last_serialized), link))
+        } else {
+            None
+        };
+
+        Self {
+            model: Rc::new(model),
+            storage,
+            storage_key,
+            last_serialized,
+            _listener: listener,
+        }
+    }
+
+    fn model(&self) -> Rc<T> {
+        Rc::clone(&self.model)
+    }
+
+    fn set_model(&mut self, model: Rc<T>) {
+        if let Ok(encoded) = serde_json::to_string(&*model) {
+            self.storage.store(&self.storage_key, Ok(encoded.clone()));
+            *self.last_serialized.borrow_mut() = encoded;
+        }
+        self.model = model;
+    }
+}
+
+impl<T: Persistent> PersistentStore<T> {
+    /// Install a `window` `storage` event listener that replaces this store's model with
+    /// whatever another tab just wrote for `storage_key`.
+    fn listen(
+        storage_key: String,
+        last_serialized: Rc<RefCell<String>>,
+        link: StoreLink<Self>,
+    ) -> Closure<dyn FnMut(StorageEvent)> {
+        let closure = Closure::wrap(Box::new(move |event: StorageEvent| {
+            if event.key().as_deref() != Some(storage_key.as_str()) {
+                return;
+            }
+
+            let new_value = match event.new_value() {
+                Some(value) => value,
+                None => return,
+            };
+
+            // Skip the event entirely if it doesn't actually change anything we don't already
+            // know about -- this is what prevents the store's own write from looping back on it.
+            if *last_serialized.borrow() == new_value {
+                return;
+            }
+
+            if let Ok(model) = serde_json::from_str::<T>(&new_value) {
+                *last_serialized.borrow_mut() = new_value;
+                link.reduce(move |current| *current = model);
+            }
+        }) as Box<dyn FnMut(StorageEvent)>);
+
+        web_sys::window()
+            .expect("no global `window` to listen for storage events on")
+            .add_event_listener_with_callback("storage", closure.as_ref().unchecked_ref())
+            .expect("failed to register storage event listener");
+
+        closure
+    }
+}
+
+impl<T> Drop for PersistentStore<T> {
+    fn drop(&mut self) {
+        if let Some(closure) = &self._listener {
+            if let Some(window) = web_sys::window() {
+                let _ = window
+                    .remove_event_listener_with_callback("storage", closure.as_ref().unchecked_ref());
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! A [Store] that updates itself through explicit actions, Redux-reducer style.
+use std::rc::Rc;
+
+use super::{Store, StoreLink};
+
+/// A model that knows how to fold an action into a new version of itself.
+pub trait Reducer: Clone + Default + 'static {
+    /// The action type this reducer accepts.
+    type Action;
+
+    /// Produce the next state from the current one and an action.
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self>;
+}
+
+/// A store whose model is a [Reducer].
+///
+/// [Dispatch](crate::dispatch::Dispatch) has no method that applies a [Reducer::Action]: the
+/// model is changed with [Dispatch::reduce](crate::dispatch::Dispatch::reduce), the same as any
+/// other store's, and [Reducer::reduce] is not called by this crate.
+pub struct ReducerStore<R: Reducer> {
+    model: Rc<R>,
+}
+
+impl<R: Reducer> Store for ReducerStore<R> {
+    type Model = R;
+
+    fn new(_link: StoreLink<Self>) -> Self {
+        Self {
+            model: Default::default(),
+        }
+    }
+
+    fn model(&self) -> Rc<R> {
+        Rc::clone(&self.model)
+    }
+
+    fn set_model(&mut self, model: Rc<R>) {
+        self.model = model;
+    }
+}